@@ -0,0 +1,161 @@
+//! Support for constructing GBFS archives at runtime.
+//!
+//! This module is only available with the `alloc` feature enabled, since assembling
+//! an archive's directory and file data requires an owned, growable buffer.
+
+use alloc::vec::Vec;
+
+use crate::header::{GBFS_HEADER_LENGTH, MAGIC};
+use crate::{Filename, GBFSError, DIR_ENTRY_LEN, FILENAME_LEN, NUM_FS_ENTRIES};
+
+/// A single named file staged for inclusion in a built archive.
+struct BuilderEntry {
+    name: [u8; FILENAME_LEN],
+    data: Vec<u8>,
+}
+
+/// Builds a GBFS archive from named byte buffers.
+///
+/// This is the inverse of [`GBFSFilesystem::from_slice`](crate::GBFSFilesystem::from_slice):
+/// stage files with [`add_file`](Self::add_file), then call [`build`](Self::build) to produce
+/// a byte buffer that round-trips back through `from_slice`. Directory entries are always
+/// emitted in ascending lexicographic order by name, as required by the sorted-directory
+/// invariant that [`GBFSFilesystem::get_file_data_by_name`](crate::GBFSFilesystem::get_file_data_by_name)
+/// relies on.
+#[derive(Default)]
+pub struct GBFSBuilder {
+    entries: Vec<BuilderEntry>,
+}
+
+impl GBFSBuilder {
+    /// Creates an empty builder with no staged files.
+    pub fn new() -> Self {
+        return GBFSBuilder {
+            entries: Vec::new(),
+        };
+    }
+
+    /// Stages a file for inclusion in the archive.
+    ///
+    /// Returns [`GBFSError::FilenameTooLong`] if `name` is longer than [`FILENAME_LEN`] bytes,
+    /// or [`GBFSError::TooManyEntries`] if the builder already holds as many files as
+    /// [`GBFSFilesystem::from_slice`](crate::GBFSFilesystem::from_slice) is able to parse back.
+    pub fn add_file(&mut self, name: &str, data: &[u8]) -> Result<&mut Self, GBFSError> {
+        if Filename::from(name).is_err() {
+            return Err(GBFSError::FilenameTooLong(FILENAME_LEN, name.len()));
+        }
+        if self.entries.len() >= NUM_FS_ENTRIES {
+            return Err(GBFSError::TooManyEntries(NUM_FS_ENTRIES, self.entries.len() + 1));
+        }
+        let mut padded_name = [0u8; FILENAME_LEN];
+        padded_name[..name.len()].copy_from_slice(name.as_bytes());
+        self.entries.push(BuilderEntry {
+            name: padded_name,
+            data: data.to_vec(),
+        });
+        return Ok(self);
+    }
+
+    /// Builds the staged files into a valid GBFS archive.
+    ///
+    /// Always produces an archive [`GBFSFilesystem::from_slice`](crate::GBFSFilesystem::from_slice)
+    /// can parse back, since [`add_file`](Self::add_file) already rejects files once the
+    /// directory is full.
+    pub fn build(mut self) -> Vec<u8> {
+        // The directory must be sorted by name for binary-search lookups to work.
+        self.entries.sort_by_key(|entry| entry.name);
+
+        let dir_off = GBFS_HEADER_LENGTH;
+        let data_start = dir_off + self.entries.len() * DIR_ENTRY_LEN;
+
+        let mut data_offsets = Vec::with_capacity(self.entries.len());
+        let mut offset = data_start;
+        for entry in &self.entries {
+            data_offsets.push(offset);
+            offset += entry.data.len();
+        }
+        let total_len = offset;
+
+        let mut out = Vec::with_capacity(total_len);
+
+        // Header
+        out.extend_from_slice(MAGIC);
+        out.extend_from_slice(&(total_len as u32).to_le_bytes());
+        out.extend_from_slice(&(dir_off as u16).to_le_bytes());
+        out.extend_from_slice(&(self.entries.len() as u16).to_le_bytes());
+        out.extend_from_slice(&[0u8; 8]); // reserved
+
+        // Directory
+        for (entry, &data_offset) in self.entries.iter().zip(&data_offsets) {
+            out.extend_from_slice(&entry.name);
+            out.extend_from_slice(&(entry.data.len() as u32).to_le_bytes());
+            out.extend_from_slice(&(data_offset as u32).to_le_bytes());
+        }
+
+        // File data
+        for entry in &self.entries {
+            out.extend_from_slice(&entry.data);
+        }
+
+        return out;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use alloc::string::ToString;
+
+    use super::*;
+    use crate::GBFSFilesystem;
+
+    #[test]
+    fn round_trips_through_from_slice() {
+        let mut builder = GBFSBuilder::new();
+        builder.add_file("b_file", b"hello").unwrap();
+        builder.add_file("a_file", b"world!").unwrap();
+        let archive = builder.build();
+
+        let fs = GBFSFilesystem::from_slice(&archive).unwrap();
+        assert_eq!(fs.get_file_data_by_name("a_file").unwrap(), b"world!");
+        assert_eq!(fs.get_file_data_by_name("b_file").unwrap(), b"hello");
+    }
+
+    #[test]
+    fn sorts_entries_by_name() {
+        let mut builder = GBFSBuilder::new();
+        builder.add_file("z", b"1").unwrap();
+        builder.add_file("a", b"2").unwrap();
+        builder.add_file("m", b"3").unwrap();
+        let archive = builder.build();
+
+        let fs = GBFSFilesystem::from_slice(&archive).unwrap();
+        let names: std::vec::Vec<std::string::String> =
+            fs.names().map(|n| n.unwrap().to_string()).collect();
+        assert_eq!(names, std::vec!["a", "m", "z"]);
+    }
+
+    #[test]
+    fn add_file_rejects_name_too_long() {
+        let mut builder = GBFSBuilder::new();
+        let long_name: std::string::String = "x".repeat(FILENAME_LEN + 1);
+        match builder.add_file(&long_name, b"data") {
+            Err(e) => assert_eq!(e, GBFSError::FilenameTooLong(FILENAME_LEN, long_name.len())),
+            Ok(_) => panic!("expected FilenameTooLong"),
+        }
+    }
+
+    #[test]
+    fn add_file_rejects_once_directory_is_full() {
+        let mut builder = GBFSBuilder::new();
+        for i in 0..NUM_FS_ENTRIES {
+            let name = std::format!("f{}", i);
+            builder.add_file(&name, b"").unwrap();
+        }
+        match builder.add_file("one_too_many", b"") {
+            Err(e) => assert_eq!(e, GBFSError::TooManyEntries(NUM_FS_ENTRIES, NUM_FS_ENTRIES + 1)),
+            Ok(_) => panic!("expected TooManyEntries"),
+        }
+    }
+}
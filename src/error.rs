@@ -18,6 +18,9 @@ pub enum GBFSError {
     Truncated,
     /// Returned when an archive contains too many entries.
     TooManyEntries(usize, usize),
+    /// Returned when a [`FileReader`](crate::FileReader) is asked to read past the end of its
+    /// file, or to seek outside its bounds.
+    UnexpectedEof,
 }
 
 impl fmt::Display for GBFSError {
@@ -39,6 +42,7 @@ impl fmt::Display for GBFSError {
                 "Encountered archive with too many entries: at most {} entries are supported, but got {}",
                 expected, actual
             ),
+            UnexpectedEof => write!(f, "Attempted to read past the end of the file or seek outside its bounds"),
         }
     }
 }
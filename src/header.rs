@@ -1,4 +1,4 @@
-const MAGIC: &[u8] = "PinEightGBFS\r\n\u{1a}\n".as_bytes();
+pub(crate) const MAGIC: &[u8] = "PinEightGBFS\r\n\u{1a}\n".as_bytes();
 pub(crate) const GBFS_HEADER_LENGTH: usize = 32;
 
 use crate::GBFSError;
@@ -4,12 +4,27 @@
 
 //! This crate enables reading of Gameboy Filesystem (`GBFS`)-formatted data.
 //! It's primarily designed for use in GBA games, and as such is fully `no_std` compatible (even `alloc` is not required).
+//!
+//! Writing archives is also supported, but requires an allocator: enable the `alloc` feature
+//! to pull in [`GBFSBuilder`].
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
 
 mod error;
 pub use error::*;
 mod header;
 use header::*;
+mod file;
+pub use file::*;
+mod reader;
+pub use reader::*;
+#[cfg(feature = "alloc")]
+mod builder;
+#[cfg(feature = "alloc")]
+pub use builder::*;
 
+use core::cmp::Ordering;
 use core::str;
 use core::u32;
 
@@ -38,8 +53,8 @@ struct GBFSFileEntry {
 }
 
 impl GBFSFileEntry {
-    /// Compare the name with a Filename.
-    fn name_is_equal(&self, name: Filename) -> Result<bool, GBFSError> {
+    /// Returns the entry's filename, with trailing null padding stripped.
+    fn filename(&self) -> Result<Filename, GBFSError> {
         // Unfortunately, the const fn constructor for GBFSFilesystem
         // can't use dynamically-sized data structures.
         // Therefore, we have to strip out the trailing nulls from the filename here.
@@ -50,10 +65,15 @@ impl GBFSFileEntry {
             Err(e) => return Err(GBFSError::Utf8Error(e)),
         };
         match Filename::from(filename_str) {
-            Err(_) => return Err(GBFSError::FilenameTooLong(FILENAME_LEN, filename_str.len())),
-            Ok(our_name) => return Ok(name == our_name),
+            Err(_) => Err(GBFSError::FilenameTooLong(FILENAME_LEN, filename_str.len())),
+            Ok(our_name) => Ok(our_name),
         }
     }
+
+    /// Compare the name with a Filename.
+    fn name_is_equal(&self, name: Filename) -> Result<bool, GBFSError> {
+        return Ok(self.filename()? == name);
+    }
 }
 
 /// A filesystem that files can be read from.
@@ -66,7 +86,10 @@ pub struct GBFSFilesystem<'a> {
     data: &'a [u8],
     /// Filesystem header
     hdr: GBFSHeader,
-    /// Directory
+    /// Directory. Entries `0..hdr.dir_num_members` are expected to be sorted in ascending
+    /// lexicographic order by name, as emitted by the canonical GBFS packer; this lets
+    /// [`get_file_data_by_name`](GBFSFilesystem::get_file_data_by_name) binary search instead
+    /// of scanning linearly.
     dir: [Option<GBFSFileEntry>; NUM_FS_ENTRIES],
 }
 
@@ -192,6 +215,12 @@ impl<'a> GBFSFilesystem<'a> {
     /// Returns a reference to the file data as a slice of u8's.
     /// An error is returned if the file does not exist or the filename is invalid.
     /// All filenames longer than `FILENAME_LEN` characters are invalid.
+    ///
+    /// This relies on the sorted-directory invariant: the canonical GBFS packer (and
+    /// [`GBFSBuilder`](crate::GBFSBuilder)) always emits directory entries in ascending
+    /// lexicographic order by name, so the lookup can binary search instead of scanning
+    /// every entry. If an archive's directory may not be sorted, use
+    /// [`get_file_data_by_name_linear`](Self::get_file_data_by_name_linear) instead.
     pub fn get_file_data_by_name(&self, str_name: &str) -> Result<&'a [u8], GBFSError> {
         let name: Filename;
         match Filename::from(str_name) {
@@ -199,6 +228,42 @@ impl<'a> GBFSFilesystem<'a> {
             Err(_) => return Err(GBFSError::FilenameTooLong(FILENAME_LEN, str_name.len())),
         }
 
+        let num_entries = self.hdr.dir_num_members as usize;
+        if num_entries == 0 {
+            return Err(GBFSError::NoSuchFile(name));
+        }
+
+        let mut query = [0u8; FILENAME_LEN];
+        query[..str_name.len()].copy_from_slice(str_name.as_bytes());
+
+        let mut low = 0usize;
+        let mut high = num_entries;
+        while low < high {
+            let mid = low + (high - low) / 2;
+            let entry = self.dir[mid]
+                .expect("Attempt to access file with nonexistent index. This is a bug in gbfs_rs.");
+            match entry.name.cmp(&query) {
+                Ordering::Less => low = mid + 1,
+                Ordering::Greater => high = mid,
+                Ordering::Equal => return Ok(self.get_file_data_by_index(mid)),
+            }
+        }
+        return Err(GBFSError::NoSuchFile(name));
+    }
+
+    /// Returns a reference to the file data as a slice of u8's, scanning the directory
+    /// linearly rather than binary-searching it.
+    ///
+    /// Unlike [`get_file_data_by_name`](Self::get_file_data_by_name), this works correctly
+    /// even if the archive's directory entries are not sorted by name, at the cost of `O(n)`
+    /// instead of `O(log n)` lookups.
+    pub fn get_file_data_by_name_linear(&self, str_name: &str) -> Result<&'a [u8], GBFSError> {
+        let name: Filename;
+        match Filename::from(str_name) {
+            Ok(val) => name = val,
+            Err(_) => return Err(GBFSError::FilenameTooLong(FILENAME_LEN, str_name.len())),
+        }
+
         // In this case, dir entries are stored in a fixed-size
         // array using an Option to denote occupied slots.
         for (i, entry) in self.dir.iter().enumerate() {
@@ -229,6 +294,61 @@ impl<'a> GBFSFilesystem<'a> {
     pub fn get_file_data_by_name_as_u32_slice(&self, name: &str) -> Result<&'a [u32], GBFSError> {
         return Ok(self.get_file_data_by_name(name)?.as_slice_of::<u32>()?);
     }
+
+    /// Returns an iterator over every file in the filesystem, yielding both its name and data.
+    pub fn entries(&self) -> GBFSEntriesIterator<'_, 'a> {
+        return GBFSEntriesIterator {
+            fs: self,
+            next_file_index: 0,
+        };
+    }
+
+    /// Returns an iterator over the name of every file in the filesystem.
+    pub fn names(&self) -> GBFSNamesIterator<'_, 'a> {
+        return GBFSNamesIterator {
+            fs: self,
+            next_file_index: 0,
+        };
+    }
+
+    /// Returns an iterator over every file whose name starts with `prefix`, yielding each
+    /// with `prefix` stripped from the returned name.
+    ///
+    /// This exploits the sorted-directory invariant (see
+    /// [`get_file_data_by_name`](Self::get_file_data_by_name)): matching entries always form
+    /// a contiguous run, so this binary-searches straight to the first match instead of
+    /// scanning the whole directory.
+    pub fn files_with_prefix<'p>(&self, prefix: &'p str) -> GBFSPrefixIterator<'_, 'a, 'p> {
+        let num_entries = self.hdr.dir_num_members as usize;
+        let prefix_bytes = prefix.as_bytes();
+        let cmp_len = prefix_bytes.len().min(FILENAME_LEN);
+
+        // Binary search for the first entry whose name is not less than `prefix`.
+        let mut low = 0usize;
+        let mut high = num_entries;
+        while low < high {
+            let mid = low + (high - low) / 2;
+            let entry = self.dir[mid]
+                .expect("Attempt to access file with nonexistent index. This is a bug in gbfs_rs.");
+            if entry.name[..cmp_len] < prefix_bytes[..cmp_len] {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+
+        return GBFSPrefixIterator {
+            fs: self,
+            prefix,
+            next_file_index: low,
+        };
+    }
+
+    /// Returns a seekable [`FileReader`] cursor over the named file's data.
+    /// An error is returned if the file does not exist or the filename is invalid.
+    pub fn get_file_reader_by_name(&self, name: &str) -> Result<FileReader<'a>, GBFSError> {
+        return Ok(FileReader::new(self.get_file_data_by_name(name)?));
+    }
 }
 
 impl<'a> IntoIterator for GBFSFilesystem<'a> {
@@ -260,3 +380,283 @@ impl<'a> Iterator for GBFSFilesystemIterator<'a> {
         }
     }
 }
+
+/// Returns the filename and data of each file in the filesystem. Returned by
+/// [`GBFSFilesystem::entries`].
+pub struct GBFSEntriesIterator<'fs, 'a> {
+    fs: &'fs GBFSFilesystem<'a>,
+    next_file_index: usize,
+}
+
+impl<'fs, 'a> Iterator for GBFSEntriesIterator<'fs, 'a> {
+    type Item = Result<File<'a>, GBFSError>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next_file_index < self.fs.hdr.dir_num_members as usize {
+            let index = self.next_file_index;
+            self.next_file_index += 1;
+            let entry = self.fs.dir[index]
+                .expect("Attempt to access file with nonexistent index. This is a bug in gbfs_rs.");
+            let data = self.fs.get_file_data_by_index(index);
+            return Some(entry.filename().map(|filename| File { filename, data }));
+        } else {
+            return None;
+        }
+    }
+}
+
+/// Returns the name of each file in the filesystem. Returned by [`GBFSFilesystem::names`].
+pub struct GBFSNamesIterator<'fs, 'a> {
+    fs: &'fs GBFSFilesystem<'a>,
+    next_file_index: usize,
+}
+
+impl<'fs, 'a> Iterator for GBFSNamesIterator<'fs, 'a> {
+    type Item = Result<FilenameString, GBFSError>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next_file_index < self.fs.hdr.dir_num_members as usize {
+            let index = self.next_file_index;
+            self.next_file_index += 1;
+            let entry = self.fs.dir[index]
+                .expect("Attempt to access file with nonexistent index. This is a bug in gbfs_rs.");
+            return Some(entry.filename());
+        } else {
+            return None;
+        }
+    }
+}
+
+/// Returns the name (with `prefix` stripped) and data of every file whose name starts with
+/// `prefix`. Returned by [`GBFSFilesystem::files_with_prefix`].
+pub struct GBFSPrefixIterator<'fs, 'a, 'p> {
+    fs: &'fs GBFSFilesystem<'a>,
+    prefix: &'p str,
+    next_file_index: usize,
+}
+
+impl<'fs, 'a, 'p> Iterator for GBFSPrefixIterator<'fs, 'a, 'p> {
+    type Item = Result<(FilenameString, &'a [u8]), GBFSError>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next_file_index >= self.fs.hdr.dir_num_members as usize {
+            return None;
+        }
+        let index = self.next_file_index;
+        let entry = self.fs.dir[index]
+            .expect("Attempt to access file with nonexistent index. This is a bug in gbfs_rs.");
+        let filename = match entry.filename() {
+            Ok(name) => name,
+            Err(e) => {
+                self.next_file_index += 1;
+                return Some(Err(e));
+            }
+        };
+        if !filename.as_str().starts_with(self.prefix) {
+            return None;
+        }
+        self.next_file_index += 1;
+        let stripped = Filename::from(&filename.as_str()[self.prefix.len()..])
+            .expect("suffix of an already-valid filename cannot exceed FILENAME_LEN");
+        let data = self.fs.get_file_data_by_index(index);
+        return Some(Ok((stripped, data)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    use super::*;
+
+    /// Hand-builds a minimal GBFS archive with entries emitted in the given order. Unlike
+    /// [`GBFSBuilder`], this does *not* sort the entries, so tests can construct archives
+    /// that violate the sorted-directory invariant on purpose.
+    fn build_archive(entries: &[(&str, &[u8])]) -> Vec<u8> {
+        let dir_off = header::GBFS_HEADER_LENGTH;
+        let data_start = dir_off + entries.len() * DIR_ENTRY_LEN;
+
+        let mut offsets = Vec::with_capacity(entries.len());
+        let mut offset = data_start;
+        for (_, data) in entries {
+            offsets.push(offset);
+            offset += data.len();
+        }
+        let total_len = offset;
+
+        let mut out = Vec::with_capacity(total_len);
+        out.extend_from_slice(header::MAGIC);
+        out.extend_from_slice(&(total_len as u32).to_le_bytes());
+        out.extend_from_slice(&(dir_off as u16).to_le_bytes());
+        out.extend_from_slice(&(entries.len() as u16).to_le_bytes());
+        out.extend_from_slice(&[0u8; 8]); // reserved
+
+        for ((name, data), &data_offset) in entries.iter().zip(&offsets) {
+            let mut padded_name = [0u8; FILENAME_LEN];
+            padded_name[..name.len()].copy_from_slice(name.as_bytes());
+            out.extend_from_slice(&padded_name);
+            out.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            out.extend_from_slice(&(data_offset as u32).to_le_bytes());
+        }
+        for (_, data) in entries {
+            out.extend_from_slice(data);
+        }
+        return out;
+    }
+
+    #[test]
+    fn binary_search_finds_files_in_sorted_directory() {
+        let archive = build_archive(&[("a", b"1"), ("m", b"2"), ("z", b"3")]);
+        let fs = GBFSFilesystem::from_slice(&archive).unwrap();
+        assert_eq!(fs.get_file_data_by_name("a").unwrap(), b"1");
+        assert_eq!(fs.get_file_data_by_name("m").unwrap(), b"2");
+        assert_eq!(fs.get_file_data_by_name("z").unwrap(), b"3");
+    }
+
+    #[test]
+    fn linear_lookup_tolerates_unsorted_directory_but_binary_search_does_not() {
+        // Deliberately out of lexicographic order.
+        let archive = build_archive(&[
+            ("z", b"1"),
+            ("y", b"2"),
+            ("x", b"3"),
+            ("w", b"4"),
+            ("a", b"5"),
+        ]);
+        let fs = GBFSFilesystem::from_slice(&archive).unwrap();
+
+        // The linear scan doesn't assume any ordering, so it finds every file regardless.
+        assert_eq!(fs.get_file_data_by_name_linear("a").unwrap(), b"5");
+        assert_eq!(fs.get_file_data_by_name_linear("z").unwrap(), b"1");
+
+        // The binary-search fast path assumes a sorted directory; given an archive that
+        // violates that invariant, it can report `NoSuchFile` for a file that is actually
+        // present. This is why `get_file_data_by_name` is documented as relying on archives
+        // produced by a canonical (sorted) packer, with `get_file_data_by_name_linear`
+        // offered as a fallback for ones that aren't.
+        assert!(fs.get_file_data_by_name("a").is_err());
+    }
+
+    #[test]
+    fn empty_directory_returns_no_such_file() {
+        let archive = build_archive(&[]);
+        let fs = GBFSFilesystem::from_slice(&archive).unwrap();
+        assert!(matches!(
+            fs.get_file_data_by_name("anything"),
+            Err(GBFSError::NoSuchFile(_))
+        ));
+        assert!(matches!(
+            fs.get_file_data_by_name_linear("anything"),
+            Err(GBFSError::NoSuchFile(_))
+        ));
+    }
+
+    #[test]
+    fn names_equal_up_to_padding_must_match_exactly() {
+        // "ab" is a prefix of "abc"; the padded comparison must not let a query for the
+        // shorter name match the longer entry, or vice versa.
+        let archive = build_archive(&[("ab", b"short"), ("abc", b"long")]);
+        let fs = GBFSFilesystem::from_slice(&archive).unwrap();
+        assert_eq!(fs.get_file_data_by_name("ab").unwrap(), b"short");
+        assert_eq!(fs.get_file_data_by_name("abc").unwrap(), b"long");
+    }
+
+    #[test]
+    fn entries_yields_every_filename_and_data() {
+        let archive = build_archive(&[("a", b"1"), ("m", b"2"), ("z", b"3")]);
+        let fs = GBFSFilesystem::from_slice(&archive).unwrap();
+
+        let entries: Vec<(std::string::String, Vec<u8>)> = fs
+            .entries()
+            .map(|e| e.unwrap())
+            .map(|f| (f.filename.to_string(), f.data.to_vec()))
+            .collect();
+        assert_eq!(
+            entries,
+            std::vec![
+                ("a".to_string(), b"1".to_vec()),
+                ("m".to_string(), b"2".to_vec()),
+                ("z".to_string(), b"3".to_vec()),
+            ]
+        );
+    }
+
+    #[test]
+    fn names_yields_every_filename() {
+        let archive = build_archive(&[("a", b"1"), ("m", b"2"), ("z", b"3")]);
+        let fs = GBFSFilesystem::from_slice(&archive).unwrap();
+
+        let names: Vec<std::string::String> = fs.names().map(|n| n.unwrap().to_string()).collect();
+        assert_eq!(names, std::vec!["a", "m", "z"]);
+    }
+
+    #[test]
+    fn entries_and_names_are_empty_for_an_empty_archive() {
+        let archive = build_archive(&[]);
+        let fs = GBFSFilesystem::from_slice(&archive).unwrap();
+        assert_eq!(fs.entries().count(), 0);
+        assert_eq!(fs.names().count(), 0);
+    }
+
+    #[test]
+    fn files_with_prefix_yields_only_matches_with_prefix_stripped() {
+        let archive = build_archive(&[
+            ("copper1Map", b"map"),
+            ("copper1Pal", b"pal"),
+            ("copper1Tiles", b"tiles"),
+            ("copper2Map", b"other"),
+        ]);
+        let fs = GBFSFilesystem::from_slice(&archive).unwrap();
+
+        let matches: Vec<(std::string::String, Vec<u8>)> = fs
+            .files_with_prefix("copper1")
+            .map(|r| r.unwrap())
+            .map(|(name, data)| (name.to_string(), data.to_vec()))
+            .collect();
+        assert_eq!(
+            matches,
+            std::vec![
+                ("Map".to_string(), b"map".to_vec()),
+                ("Pal".to_string(), b"pal".to_vec()),
+                ("Tiles".to_string(), b"tiles".to_vec()),
+            ]
+        );
+    }
+
+    #[test]
+    fn files_with_prefix_yields_nothing_when_no_name_matches() {
+        let archive = build_archive(&[("a", b"1"), ("b", b"2")]);
+        let fs = GBFSFilesystem::from_slice(&archive).unwrap();
+        assert_eq!(fs.files_with_prefix("nope").count(), 0);
+    }
+
+    #[test]
+    fn files_with_prefix_on_empty_archive_yields_nothing() {
+        let archive = build_archive(&[]);
+        let fs = GBFSFilesystem::from_slice(&archive).unwrap();
+        assert_eq!(fs.files_with_prefix("anything").count(), 0);
+    }
+
+    #[test]
+    fn files_with_prefix_data_outlives_the_prefix_string() {
+        let archive = build_archive(&[("copper1Map", b"map"), ("copper1Pal", b"pal")]);
+        let fs = GBFSFilesystem::from_slice(&archive).unwrap();
+
+        // `prefix` is an owned, short-lived `String` built at runtime (e.g. `format!(...)`),
+        // not a `'static` literal, and it's dropped at the end of this block. The data
+        // slices `files_with_prefix` yields borrow from the archive, not from `prefix`, so
+        // they must remain usable here even though `prefix` no longer exists. If `prefix`'s
+        // lifetime were ever unified with the archive's, this wouldn't compile.
+        let collected: Vec<(std::string::String, &[u8])> = {
+            let prefix = std::format!("copper{}", 1);
+            fs.files_with_prefix(&prefix)
+                .map(|r| r.unwrap())
+                .map(|(name, data)| (name.to_string(), data))
+                .collect()
+        };
+
+        assert_eq!(
+            collected,
+            std::vec![("Map".to_string(), &b"map"[..]), ("Pal".to_string(), &b"pal"[..])]
+        );
+    }
+}
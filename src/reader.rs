@@ -0,0 +1,151 @@
+//! A seekable cursor over a single file's data.
+//!
+//! This mirrors the shape of `embedded-io`/`core2`'s `Read` + `Seek` traits without
+//! depending on either crate, so the cursor stays usable in this crate's `no_std`,
+//! allocation-free core.
+
+use crate::GBFSError;
+
+/// Where a [`FileReader::seek`] offset is measured from.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SeekFrom {
+    /// Measured forward from the start of the file.
+    Start(u32),
+    /// Measured relative to the current cursor position.
+    Current(i32),
+    /// Measured relative to the end of the file; negative offsets move backward.
+    End(i32),
+}
+
+/// A cursor over a single file's data, supporting incremental, position-tracked reads.
+///
+/// Holds a borrowed file data slice plus a read position, so it can be built directly from
+/// the slice returned by [`GBFSFilesystem::get_file_data_by_name`](crate::GBFSFilesystem::get_file_data_by_name)
+/// without copying or allocating.
+#[derive(Debug, Clone)]
+pub struct FileReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> FileReader<'a> {
+    /// Creates a new cursor over `data`, positioned at the start.
+    pub const fn new(data: &'a [u8]) -> Self {
+        return FileReader { data, pos: 0 };
+    }
+
+    /// Returns the number of unread bytes remaining in the file.
+    pub fn remaining(&self) -> usize {
+        return self.data.len() - self.pos;
+    }
+
+    /// Reads up to `buf.len()` bytes into `buf`, returning the number of bytes read.
+    /// Returns `0` once the cursor has reached the end of the file.
+    pub fn read(&mut self, buf: &mut [u8]) -> usize {
+        let n = buf.len().min(self.remaining());
+        buf[..n].copy_from_slice(&self.data[self.pos..self.pos + n]);
+        self.pos += n;
+        return n;
+    }
+
+    /// Reads exactly `buf.len()` bytes, filling `buf` entirely.
+    ///
+    /// Returns [`GBFSError::UnexpectedEof`] (without advancing the cursor) if fewer than
+    /// `buf.len()` bytes remain in the file.
+    pub fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), GBFSError> {
+        if buf.len() > self.remaining() {
+            return Err(GBFSError::UnexpectedEof);
+        }
+        self.read(buf);
+        return Ok(());
+    }
+
+    /// Moves the cursor to a new position, relative to the start, current position, or end
+    /// of the file. Returns the new absolute position.
+    ///
+    /// Returns [`GBFSError::UnexpectedEof`] (without moving the cursor) if the resulting
+    /// position would fall outside the bounds of the file.
+    pub fn seek(&mut self, pos: SeekFrom) -> Result<usize, GBFSError> {
+        let new_pos = match pos {
+            SeekFrom::Start(offset) => offset as i64,
+            SeekFrom::Current(offset) => self.pos as i64 + offset as i64,
+            SeekFrom::End(offset) => self.data.len() as i64 + offset as i64,
+        };
+        if new_pos < 0 || new_pos as usize > self.data.len() {
+            return Err(GBFSError::UnexpectedEof);
+        }
+        self.pos = new_pos as usize;
+        return Ok(self.pos);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_exact_advances_the_cursor() {
+        let mut reader = FileReader::new(b"hello world");
+        let mut buf = [0u8; 5];
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(&buf, b"hello");
+        assert_eq!(reader.remaining(), 6);
+
+        let mut rest = [0u8; 6];
+        reader.read_exact(&mut rest).unwrap();
+        assert_eq!(&rest, b" world");
+        assert_eq!(reader.remaining(), 0);
+    }
+
+    #[test]
+    fn read_exact_past_end_of_file_returns_unexpected_eof_without_advancing() {
+        let mut reader = FileReader::new(b"abc");
+        let mut buf = [0u8; 10];
+        assert_eq!(reader.read_exact(&mut buf), Err(GBFSError::UnexpectedEof));
+        // The cursor should not have moved, so a smaller read still succeeds from the start.
+        let mut small = [0u8; 3];
+        reader.read_exact(&mut small).unwrap();
+        assert_eq!(&small, b"abc");
+    }
+
+    #[test]
+    fn read_returns_fewer_bytes_than_requested_at_end_of_file() {
+        let mut reader = FileReader::new(b"ab");
+        let mut buf = [0u8; 5];
+        assert_eq!(reader.read(&mut buf), 2);
+        assert_eq!(&buf[..2], b"ab");
+        assert_eq!(reader.read(&mut buf), 0);
+    }
+
+    #[test]
+    fn seek_from_start_current_and_end() {
+        let mut reader = FileReader::new(b"0123456789");
+
+        assert_eq!(reader.seek(SeekFrom::Start(4)), Ok(4));
+        assert_eq!(reader.remaining(), 6);
+
+        assert_eq!(reader.seek(SeekFrom::Current(2)), Ok(6));
+        assert_eq!(reader.remaining(), 4);
+
+        assert_eq!(reader.seek(SeekFrom::Current(-3)), Ok(3));
+
+        assert_eq!(reader.seek(SeekFrom::End(0)), Ok(10));
+        assert_eq!(reader.remaining(), 0);
+
+        assert_eq!(reader.seek(SeekFrom::End(-2)), Ok(8));
+        assert_eq!(reader.remaining(), 2);
+    }
+
+    #[test]
+    fn seek_out_of_bounds_returns_unexpected_eof_without_moving() {
+        let mut reader = FileReader::new(b"abc");
+        reader.seek(SeekFrom::Start(1)).unwrap();
+
+        assert_eq!(reader.seek(SeekFrom::Start(4)), Err(GBFSError::UnexpectedEof));
+        assert_eq!(reader.seek(SeekFrom::Current(-5)), Err(GBFSError::UnexpectedEof));
+        assert_eq!(reader.seek(SeekFrom::End(1)), Err(GBFSError::UnexpectedEof));
+
+        // The cursor should still be where it was before any of the failed seeks.
+        assert_eq!(reader.remaining(), 2);
+    }
+}